@@ -0,0 +1,98 @@
+use {
+    crate::Config,
+    anyhow::Context,
+    rayon::prelude::*,
+    std::{
+        collections::BTreeMap,
+        fs,
+        path::{Path, PathBuf},
+        process,
+    },
+};
+
+/// Open multiple files and directories, grouping them by their resolved program.
+///
+/// Directories are walked recursively. Because resolving a program detects each file's MIME type,
+/// which is I/O- and potentially subprocess-bound, the detection phase is parallelized with
+/// `rayon`; per-file errors are collected rather than aborting the whole run. Since `exec` can
+/// launch only one program, files are grouped by resolved program and each program is spawned once
+/// with all of its matching files as arguments. Programs are run one group at a time so that
+/// interactive, tty-bound programs do not fight over the terminal. A summary of successes and
+/// failures is printed at the end.
+///
+/// # Errors
+///
+/// Fails if a directory cannot be read.
+pub fn run(paths: &[String], config: &Config) -> anyhow::Result<()> {
+    let mut files = Vec::new();
+    for path in paths {
+        collect_files(Path::new(path), &mut files)?;
+    }
+
+    let resolved: Vec<Result<(PathBuf, String), (PathBuf, anyhow::Error)>> = files
+        .par_iter()
+        .map(|file| match config.get_program(file) {
+            Ok(program) => Ok((file.clone(), program.to_string())),
+            Err(error) => Err((file.clone(), error)),
+        })
+        .collect();
+
+    let mut groups: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    let mut failed = 0usize;
+    for result in resolved {
+        match result {
+            Ok((file, program)) => groups.entry(program).or_default().push(file),
+            Err((file, error)) => {
+                eprintln!("Error: {}: {error:#}", file.display());
+                failed += 1;
+            }
+        }
+    }
+
+    // Run one program at a time, waiting for each before starting the next: interactive,
+    // tty-bound programs such as an editor or pager cannot share the terminal, so launching
+    // them concurrently would be unusable.
+    let mut opened = 0usize;
+    for (program, files) in groups {
+        let result = process::Command::new(&program)
+            .args(&files)
+            .spawn()
+            .and_then(|mut child| child.wait());
+        match result {
+            Ok(status) if status.success() => opened += files.len(),
+            Ok(status) => {
+                eprintln!("Error: '{program}' exited with {status}");
+                failed += files.len();
+            }
+            Err(error) => {
+                eprintln!("Error: failed to run '{program}': {error}");
+                failed += files.len();
+            }
+        }
+    }
+
+    println!("Opened {opened} file(s); {failed} failed");
+    Ok(())
+}
+
+/// Recursively collect the files reachable from `path` into `files`.
+///
+/// A `path` that is not a directory is added as-is.
+///
+/// # Errors
+///
+/// Fails if a directory cannot be read.
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    if path.is_dir() {
+        let entries = fs::read_dir(path)
+            .with_context(|| format!("Failed to read directory {}", path.display()))?;
+        for entry in entries {
+            let entry = entry
+                .with_context(|| format!("Failed to read directory {}", path.display()))?;
+            collect_files(&entry.path(), files)?;
+        }
+    } else {
+        files.push(path.to_path_buf());
+    }
+    Ok(())
+}
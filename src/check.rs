@@ -0,0 +1,87 @@
+use {
+    crate::{Backend, MimeType},
+    anyhow::Context,
+    std::{
+        fmt::{self, Display, Formatter},
+        io,
+        path::Path,
+    },
+};
+
+/// A file whose extension disagrees with its detected content type.
+#[derive(Debug)]
+struct Finding {
+    path: String,
+    mime: MimeType,
+    current_extension: Option<String>,
+    recommended_extension: &'static str,
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let current = self.current_extension.as_deref().unwrap_or("-");
+        write!(
+            f,
+            "{}\t{}\t{}\t{}",
+            self.path, self.mime, current, self.recommended_extension
+        )
+    }
+}
+
+/// Check the given files, reporting any whose extension disagrees with its detected content type.
+///
+/// Each finding is written to `out` as a tab-separated row of the file path, the detected MIME
+/// type, the current extension (or `-`), and the recommended extension, so the output can be fed
+/// into a rename command. Files that cannot be read, or whose type has no known extension, are
+/// skipped (errors are reported on stderr) so that one bad file does not abort the whole run.
+///
+/// Only types with canonical extensions in the native signature table can be checked (see
+/// [`MimeType::extensions`]); the `text/plain` fallback and any type outside that table have no
+/// known extensions and are therefore never flagged.
+///
+/// # Errors
+///
+/// Fails if a finding cannot be written to `out`.
+pub fn run<W: io::Write>(paths: &[String], backend: Backend, mut out: W) -> anyhow::Result<()> {
+    for path in paths {
+        match check_file(path, backend) {
+            Ok(Some(finding)) => {
+                writeln!(out, "{finding}").context("Failed to write finding")?;
+            }
+            Ok(None) => {}
+            Err(error) => eprintln!("Error: {error:#}"),
+        }
+    }
+    Ok(())
+}
+
+/// Check a single file, returning a [`Finding`] if its extension disagrees with its content type.
+///
+/// Returns `None` if the extension already matches or if the detected type has no known extension.
+///
+/// # Errors
+///
+/// Fails if the MIME type of the file cannot be determined.
+fn check_file(path: &str, backend: Backend) -> anyhow::Result<Option<Finding>> {
+    let mime = MimeType::detect(path, backend)?;
+    let extensions = mime.extensions();
+    let Some(&recommended) = extensions.first() else {
+        return Ok(None);
+    };
+    let current = Path::new(path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().into_owned());
+    let matches = current
+        .as_deref()
+        .is_some_and(|ext| extensions.iter().any(|canon| canon.eq_ignore_ascii_case(ext)));
+    if matches {
+        Ok(None)
+    } else {
+        Ok(Some(Finding {
+            path: path.to_string(),
+            mime,
+            current_extension: current,
+            recommended_extension: recommended,
+        }))
+    }
+}
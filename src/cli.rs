@@ -1,16 +1,19 @@
 use {
-    crate::Config,
+    crate::{batch, check, Config},
     anyhow::Context,
-    std::{
-        env, io,
-        os::unix::process::CommandExt,
-        process::{self, Command},
-    },
+    std::{env, io, os::unix::process::CommandExt, path::Path, process},
 };
 
 macro_rules! usage {
     () => {
-        concat!("Usage: ", env!("CARGO_PKG_NAME"), " <FILE>\n")
+        concat!(
+            "Usage: ",
+            env!("CARGO_PKG_NAME"),
+            " <PATH>...\n",
+            "       ",
+            env!("CARGO_PKG_NAME"),
+            " check <FILE>...\n",
+        )
     };
 }
 
@@ -27,7 +30,10 @@ macro_rules! error_help_body {
 macro_rules! help_body {
     () => {
         r#"
-Open FILE with the appropriate program
+Open each PATH with the appropriate program, walking directories recursively
+
+Commands:
+  check <FILE>...  Report files whose extension disagrees with their content
 
 Options:
   -h, --help     Print this help message and exit
@@ -44,7 +50,16 @@ const ERROR_HELP: &str = concat!(usage!(), error_help_body!());
 /// The CLI arguments.
 #[derive(Debug)]
 pub struct Cli {
-    file: String,
+    command: Command,
+}
+
+/// The subcommand selected on the command line.
+#[derive(Debug)]
+enum Command {
+    /// Open each path with its configured program, walking directories recursively.
+    Open { paths: Vec<String> },
+    /// Report files whose extension disagrees with their detected content type.
+    Check { paths: Vec<String> },
 }
 
 impl Cli {
@@ -80,14 +95,20 @@ impl Cli {
             process::exit(0);
         }
 
-        if args.len() != 2 {
-            Self::print_error_help(io::stderr());
-            process::exit(1);
-        }
+        let command = if args[1] == "check" {
+            let paths = args[2..].to_vec();
+            if paths.is_empty() {
+                Self::print_error_help(io::stderr());
+                process::exit(1);
+            }
+            Command::Check { paths }
+        } else {
+            Command::Open {
+                paths: args[1..].to_vec(),
+            }
+        };
 
-        Self {
-            file: args[1].clone(),
-        }
+        Self { command }
     }
 
     /// Print the help message.
@@ -103,7 +124,21 @@ impl Cli {
     /// Run the program with the given arguments.
     pub fn run(&self) -> anyhow::Result<()> {
         let config = Config::load()?;
-        let program = config.get_program(&self.file)?;
-        Err(Command::new(program).arg(&self.file).exec()).context("Failed to open the file")
+        match &self.command {
+            // A single file is opened via exec, replacing this process as before. Multiple paths
+            // or a directory require launching several programs, which exec cannot do, so they go
+            // through the batch path instead.
+            Command::Open { paths } => {
+                if let [path] = paths.as_slice() {
+                    if !Path::new(path).is_dir() {
+                        let program = config.get_program(path)?;
+                        return Err(process::Command::new(program).arg(path).exec())
+                            .context("Failed to open the file");
+                    }
+                }
+                batch::run(paths, &config)
+            }
+            Command::Check { paths } => check::run(paths, config.backend(), io::stdout()),
+        }
     }
 }
@@ -1,11 +1,12 @@
 use {
-    crate::MimeType,
+    crate::{Backend, MimeType},
     anyhow::{anyhow, bail, Context},
+    glob::Pattern,
     std::{
         env,
         ffi::{OsStr, OsString},
         fs::File,
-        io::{self, BufRead, BufReader},
+        io::{BufRead, BufReader},
         path::{Path, PathBuf},
         str::FromStr,
     },
@@ -22,30 +23,75 @@ fn home_dir() -> anyhow::Result<PathBuf> {
         .map(PathBuf::from)
 }
 
-/// Get the path to the configuration file.
-///
-/// # Errors
+/// The name of the configuration file within each configuration directory.
+const CONFIG_FILE_NAME: &str = "uf.conf";
+
+/// Get the user configuration directory.
 ///
-/// Fails if the home directory of the current user cannot be determined.
-fn config_path() -> anyhow::Result<PathBuf> {
-    home_dir().map(|mut home_dir| {
-        home_dir.push(".config/uf.conf");
+/// Honors `XDG_CONFIG_HOME`, falling back to `~/.config`. Returns `None` if neither can be
+/// determined.
+fn config_home() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+    home_dir().ok().map(|mut home_dir| {
+        home_dir.push(".config");
         home_dir
     })
 }
 
+/// Get the system configuration directories, in decreasing order of priority.
+///
+/// Honors `XDG_CONFIG_DIRS`, falling back to `/etc/xdg`.
+fn config_dirs() -> Vec<PathBuf> {
+    match env::var_os("XDG_CONFIG_DIRS").filter(|dirs| !dirs.is_empty()) {
+        Some(dirs) => env::split_paths(&dirs).collect(),
+        None => vec![PathBuf::from("/etc/xdg")],
+    }
+}
+
+/// Get the existing configuration files, in decreasing order of priority.
+///
+/// The user configuration file comes first, so that `get_program`'s first-match semantics give it
+/// precedence over the system files that follow.
+fn config_files() -> Vec<PathBuf> {
+    config_home()
+        .into_iter()
+        .chain(config_dirs())
+        .map(|mut dir| {
+            dir.push(CONFIG_FILE_NAME);
+            dir
+        })
+        .filter(|path| path.is_file())
+        .collect()
+}
+
 /// Configuration.
 #[derive(Debug)]
 pub struct Config {
+    backend: Option<Backend>,
     mappings: Vec<Mapping>,
 }
 
+/// A single parsed configuration directive.
+#[derive(Debug)]
+enum Directive {
+    /// Select the MIME detection backend.
+    Backend(Backend),
+    /// Map matching files to a program.
+    Mapping(Mapping),
+}
+
 #[derive(Debug)]
 enum Mapping {
     Extension {
         extension: OsString,
         program: String,
     },
+    Glob {
+        pattern: Pattern,
+        program: String,
+    },
     Mime {
         mime: MimeTypeKey,
         program: String,
@@ -54,12 +100,19 @@ enum Mapping {
 
 impl Mapping {
     /// Return the program to use if the mapping matches the file, or `None` if it does not.
-    fn get_program(&self, mime: &MimeType, extension: Option<&OsStr>) -> Option<&str> {
+    fn get_program(&self, path: &Path, mime: &MimeType, extension: Option<&OsStr>) -> Option<&str> {
         match self {
             Self::Extension {
                 extension: map_extension,
                 program,
             } if Some(map_extension.as_os_str()) == extension => Some(program),
+            Self::Glob { pattern, program }
+                if path.file_name().is_some_and(|name| {
+                    pattern.matches(&name.to_string_lossy())
+                }) =>
+            {
+                Some(program)
+            }
             Self::Mime {
                 mime: map_mime,
                 program,
@@ -120,29 +173,39 @@ enum MimeSubtypeKey {
 }
 
 impl Config {
-    /// Load the configuration file.
+    /// Load and merge the configuration files discovered according to the XDG Base Directory
+    /// specification.
+    ///
+    /// The user configuration file takes precedence over the system files: their mappings are
+    /// concatenated user-first, so `get_program`'s first-match semantics prefer the user's rules,
+    /// and the backend is taken from the highest-priority file that selects one.
     ///
     /// # Errors
     ///
     /// Fails in any of the following cases:
-    /// - The location of the configuration file cannot be determined.
-    /// - The configuration file cannot be opened.
-    /// - The configuration file cannot be read.
-    /// - The configuration file is invalid.
+    /// - No configuration file is found.
+    /// - A configuration file cannot be opened or read.
+    /// - A configuration file is invalid.
     pub fn load() -> anyhow::Result<Self> {
-        let config_path = config_path()?;
-        let config_file = File::open(&config_path).map_err(|error| match error.kind() {
-            io::ErrorKind::NotFound => {
-                anyhow!("Config file not found: {}", config_path.display())
-            }
-            _ => anyhow::Error::new(error).context(format!(
-                "Failed to open config file: {}",
-                config_path.display()
-            )),
-        })?;
-        let config_reader = BufReader::new(config_file);
-
-        Self::parse(config_reader)
+        let config_files = config_files();
+        if config_files.is_empty() {
+            bail!("No config file found");
+        }
+
+        let mut backend = None;
+        let mut mappings = Vec::new();
+        for config_path in config_files {
+            let config_file = File::open(&config_path).with_context(|| {
+                format!("Failed to open config file: {}", config_path.display())
+            })?;
+            let config = Self::parse(BufReader::new(config_file)).with_context(|| {
+                format!("Failed to parse config file: {}", config_path.display())
+            })?;
+            backend = backend.or(config.backend);
+            mappings.extend(config.mappings);
+        }
+
+        Ok(Self { backend, mappings })
     }
 
     /// Parse the configuration file.
@@ -157,7 +220,7 @@ impl Config {
         reason = "Using get() instead of [] would be unnecessarily verbose."
     )]
     fn parse<R: BufRead>(reader: R) -> anyhow::Result<Self> {
-        let mappings = reader
+        let directives = reader
             .lines()
             .enumerate()
             .map(|(line_index, line)| (line_index + 1, line))
@@ -171,21 +234,36 @@ impl Config {
                     return Ok(None);
                 }
                 let parts: Vec<&str> = line.split_whitespace().collect();
+                if parts[0] == "backend" {
+                    if parts.len() != 2 {
+                        bail!("Invalid line: {}", line);
+                    }
+                    return Ok(Some(Directive::Backend(match parts[1] {
+                        "native" => Backend::Native,
+                        "file" => Backend::File,
+                        _ => bail!("Invalid backend: {}", parts[1]),
+                    })));
+                }
                 if parts.len() != 3 {
                     bail!("Invalid line: {}", line);
                 }
                 let program = parts[2].to_string();
                 match parts[0] {
-                    "ext" => Ok(Some(Mapping::Extension {
+                    "ext" => Ok(Some(Directive::Mapping(Mapping::Extension {
                         extension: OsString::from(parts[1]),
                         program,
-                    })),
-                    "mime" => Ok(Some(Mapping::Mime {
+                    }))),
+                    "glob" => Ok(Some(Directive::Mapping(Mapping::Glob {
+                        pattern: Pattern::new(parts[1])
+                            .with_context(|| format!("Invalid line: {line}"))?,
+                        program,
+                    }))),
+                    "mime" => Ok(Some(Directive::Mapping(Mapping::Mime {
                         mime: parts[1]
                             .parse()
                             .with_context(|| format!("Invalid line: {line}"))?,
                         program,
-                    })),
+                    }))),
                     _ => bail!("Invalid line: {}", line),
                 }
             })
@@ -193,7 +271,21 @@ impl Config {
             .collect::<anyhow::Result<Vec<_>>>()
             .context("Failed to parse config file")?;
 
-        Ok(Self { mappings })
+        let mut backend = None;
+        let mut mappings = Vec::new();
+        for directive in directives {
+            match directive {
+                Directive::Backend(selected) => backend = Some(selected),
+                Directive::Mapping(mapping) => mappings.push(mapping),
+            }
+        }
+
+        Ok(Self { backend, mappings })
+    }
+
+    /// Get the configured MIME detection backend.
+    pub fn backend(&self) -> Backend {
+        self.backend.unwrap_or_default()
     }
 
     /// Get the program configured for opening a file.
@@ -204,12 +296,13 @@ impl Config {
     /// - The MIME type of the file cannot be determined.
     /// - No program is configured for the file.
     pub fn get_program<P: AsRef<Path>>(&self, file_path: P) -> anyhow::Result<&str> {
-        let extension = file_path.as_ref().extension();
-        let mime = MimeType::detect(&file_path)?;
+        let path = file_path.as_ref();
+        let extension = path.extension();
+        let mime = MimeType::detect(path, self.backend())?;
 
         self.mappings
             .iter()
-            .find_map(|mapping| mapping.get_program(&mime, extension))
+            .find_map(|mapping| mapping.get_program(path, &mime, extension))
             .ok_or_else(|| match extension {
                 Some(extension) => anyhow!(
                     "No program found for MIME type '{mime}', extension '{}'",
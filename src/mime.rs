@@ -2,25 +2,229 @@ use {
     anyhow::{anyhow, Context},
     std::{
         fmt::{self, Display, Formatter},
+        fs::File,
+        io::Read,
         path::Path,
         process::Command,
-        str,
+        str::{self, FromStr},
     },
 };
 
+/// The number of leading bytes read from a file for native magic-number detection.
+const SNIFF_LEN: usize = 8192;
+
 #[derive(Debug)]
 pub struct MimeType {
     supertype: String,
     subtype: String,
 }
 
+/// The backend used to detect the MIME type of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Pure-Rust detection based on a table of magic-number signatures.
+    Native,
+    /// The external `file` command.
+    File,
+}
+
+impl Default for Backend {
+    fn default() -> Self {
+        Self::Native
+    }
+}
+
+/// A single byte in a magic-number pattern.
+///
+/// `None` is a wildcard that matches any byte; `Some(byte)` matches exactly `byte`.
+type PatternByte = Option<u8>;
+
+/// A magic-number signature: a byte pattern expected at a fixed offset that identifies a MIME type.
+struct Signature {
+    /// The offset at which `pattern` is expected.
+    offset: usize,
+    /// The byte pattern, where wildcards match any byte.
+    pattern: &'static [PatternByte],
+    /// The MIME type identified by this signature, as `supertype/subtype`.
+    mime: &'static str,
+    /// The canonical file extensions for this MIME type, recommended one first.
+    extensions: &'static [&'static str],
+}
+
+impl Signature {
+    /// Return whether this signature matches the given buffer.
+    fn matches(&self, buffer: &[u8]) -> bool {
+        let end = self.offset + self.pattern.len();
+        if buffer.len() < end {
+            return false;
+        }
+        self.pattern
+            .iter()
+            .zip(&buffer[self.offset..end])
+            .all(|(expected, actual)| expected.is_none_or(|byte| byte == *actual))
+    }
+}
+
+/// Shorthand for a concrete (non-wildcard) pattern byte.
+const fn b(byte: u8) -> PatternByte {
+    Some(byte)
+}
+
+/// A wildcard pattern byte.
+const ANY: PatternByte = None;
+
+/// The table of magic-number signatures, tried in order.
+const SIGNATURES: &[Signature] = &[
+    Signature {
+        offset: 0,
+        pattern: &[b(0xFF), b(0xD8), b(0xFF)],
+        mime: "image/jpeg",
+        extensions: &["jpg", "jpeg"],
+    },
+    Signature {
+        offset: 0,
+        pattern: &[b(0x89), b(0x50), b(0x4E), b(0x47), b(0x0D), b(0x0A), b(0x1A), b(0x0A)],
+        mime: "image/png",
+        extensions: &["png"],
+    },
+    Signature {
+        offset: 0,
+        pattern: &[b(0x47), b(0x49), b(0x46), b(0x38)],
+        mime: "image/gif",
+        extensions: &["gif"],
+    },
+    Signature {
+        offset: 0,
+        pattern: &[b(0x42), b(0x4D)],
+        mime: "image/bmp",
+        extensions: &["bmp"],
+    },
+    Signature {
+        offset: 0,
+        pattern: &[
+            b(0x52), b(0x49), b(0x46), b(0x46), ANY, ANY, ANY, ANY, b(0x57), b(0x45), b(0x42),
+            b(0x50),
+        ],
+        mime: "image/webp",
+        extensions: &["webp"],
+    },
+    Signature {
+        offset: 0,
+        pattern: &[
+            b(0x52), b(0x49), b(0x46), b(0x46), ANY, ANY, ANY, ANY, b(0x41), b(0x49), b(0x46),
+            b(0x46),
+        ],
+        mime: "audio/aiff",
+        extensions: &["aiff", "aif"],
+    },
+    Signature {
+        offset: 0,
+        pattern: &[
+            b(0x52), b(0x49), b(0x46), b(0x46), ANY, ANY, ANY, ANY, b(0x57), b(0x41), b(0x56),
+            b(0x45),
+        ],
+        mime: "audio/wav",
+        extensions: &["wav"],
+    },
+    Signature {
+        offset: 0,
+        pattern: &[b(0x4F), b(0x67), b(0x67), b(0x53)],
+        mime: "audio/ogg",
+        extensions: &["ogg", "oga"],
+    },
+    Signature {
+        offset: 0,
+        pattern: &[b(0x66), b(0x4C), b(0x61), b(0x43)],
+        mime: "audio/flac",
+        extensions: &["flac"],
+    },
+    Signature {
+        offset: 0,
+        pattern: &[b(0x49), b(0x44), b(0x33)],
+        mime: "audio/mpeg",
+        extensions: &["mp3"],
+    },
+    Signature {
+        offset: 0,
+        pattern: &[b(0x25), b(0x50), b(0x44), b(0x46), b(0x2D)],
+        mime: "application/pdf",
+        extensions: &["pdf"],
+    },
+    Signature {
+        offset: 0,
+        pattern: &[b(0x50), b(0x4B), b(0x03), b(0x04)],
+        mime: "application/zip",
+        extensions: &["zip"],
+    },
+    Signature {
+        offset: 0,
+        pattern: &[b(0x1F), b(0x8B)],
+        mime: "application/gzip",
+        extensions: &["gz"],
+    },
+    Signature {
+        offset: 0,
+        pattern: &[b(0x7F), b(0x45), b(0x4C), b(0x46)],
+        mime: "application/x-executable",
+        extensions: &[],
+    },
+];
+
 impl MimeType {
-    /// Get the MIME type of a file.
+    /// Get the MIME type of a file using the given backend.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the MIME type cannot be determined.
+    pub fn detect<P: AsRef<Path>>(file_path: P, backend: Backend) -> anyhow::Result<Self> {
+        match backend {
+            Backend::Native => Self::detect_native(file_path),
+            Backend::File => Self::detect_file(file_path),
+        }
+    }
+
+    /// Detect the MIME type of a file by matching its leading bytes against [`SIGNATURES`].
+    ///
+    /// Falls back to `text/plain` if no signature matches but the sniffed content is valid UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file cannot be opened or read, or if no MIME type can be determined.
+    fn detect_native<P: AsRef<Path>>(file_path: P) -> anyhow::Result<Self> {
+        let file_path = file_path.as_ref();
+        let mut file =
+            File::open(file_path).with_context(|| format!("Failed to open {}", file_path.display()))?;
+        let mut buffer = Vec::with_capacity(SNIFF_LEN);
+        file.take(SNIFF_LEN as u64)
+            .read_to_end(&mut buffer)
+            .with_context(|| format!("Failed to read {}", file_path.display()))?;
+
+        if let Some(signature) = SIGNATURES.iter().find(|sig| sig.matches(&buffer)) {
+            return signature.mime.parse();
+        }
+
+        let is_text = match str::from_utf8(&buffer) {
+            Ok(_) => true,
+            // The sniff window may end in the middle of a multi-byte sequence; treat the
+            // content as text when the only problem is a truncated final codepoint.
+            Err(error) => error.error_len().is_none(),
+        };
+        if is_text {
+            return "text/plain".parse();
+        }
+
+        Err(anyhow!(
+            "Could not determine MIME type of {}",
+            file_path.display()
+        ))
+    }
+
+    /// Detect the MIME type of a file using the external `file` command.
     ///
     /// # Errors
     ///
     /// Fails if the MIME type cannot be determined.
-    pub fn detect<P: AsRef<Path>>(file_path: P) -> anyhow::Result<Self> {
+    fn detect_file<P: AsRef<Path>>(file_path: P) -> anyhow::Result<Self> {
         let output = Command::new("file")
             .arg("--brief")
             .arg("--dereference")
@@ -32,14 +236,10 @@ impl MimeType {
         if output.status.success() {
             let mime_type =
                 str::from_utf8(&output.stdout).context("'file' output is invalid UTF-8")?;
-            let (supertype, subtype) = mime_type
+            mime_type
                 .trim()
-                .split_once('/')
-                .with_context(|| format!("'file' output is not a valid MIME type: {mime_type}"))?;
-            Ok(Self {
-                supertype: supertype.to_string(),
-                subtype: subtype.to_string(),
-            })
+                .parse()
+                .with_context(|| format!("'file' output is not a valid MIME type: {mime_type}"))
         } else {
             let error = String::from_utf8_lossy(&output.stderr);
             Err(anyhow!("{}", error).context("'file' command failed"))
@@ -53,6 +253,34 @@ impl MimeType {
     pub fn subtype(&self) -> &str {
         &self.subtype
     }
+
+    /// Return the canonical file extensions associated with this MIME type.
+    ///
+    /// The extensions come from the native signature table; the first entry, if any, is the
+    /// recommended extension. Types that are not in the table — including the `text/plain`
+    /// fallback and anything the `file` backend reports but the table does not cover — have no
+    /// known extensions and yield an empty slice.
+    pub fn extensions(&self) -> &'static [&'static str] {
+        let mime = self.to_string();
+        SIGNATURES
+            .iter()
+            .find(|sig| sig.mime.eq_ignore_ascii_case(&mime))
+            .map_or(&[][..], |sig| sig.extensions)
+    }
+}
+
+impl FromStr for MimeType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (supertype, subtype) = s
+            .split_once('/')
+            .with_context(|| format!("Not a valid MIME type: {s}"))?;
+        Ok(Self {
+            supertype: supertype.to_string(),
+            subtype: subtype.to_string(),
+        })
+    }
 }
 
 impl Display for MimeType {